@@ -1,17 +1,103 @@
 use pyo3::prelude::*;
-use pyo3::exceptions::{PyKeyError, PyValueError};
+use pyo3::exceptions::{PyKeyError, PyValueError, PyBlockingIOError, PyOverflowError};
 use std::{slice, usize};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use memmap2::MmapMut;
 
 use crate::iterator::IndexGenerator;
+use crate::locking::{acquire_spin, try_acquire, release, check_uid, validate_header, UNLOCKED};
 
+/// How many probe steps a key currently sits from its home bucket (`key & mask`).
+/// Not stored on the slot: always recomputed from the key and the slot's own
+/// index, so the on-wire `Slot` layout stays unchanged.
+fn probe_distance(key: usize, idx: usize, mask: usize) -> usize {
+    idx.wrapping_sub(key & mask) & mask
+}
+
+/// Whether `h` lies in the cyclic interval `(i, j]` modulo `capacity`.
+fn in_cyclic_interval(h: usize, i: usize, j: usize) -> bool {
+    if i <= j {
+        h > i && h <= j
+    } else {
+        h > i || h <= j
+    }
+}
+
+/// Robin Hood insertion into a table with no per-slot locking needed, e.g. a fresh
+/// buffer being built up by `rehash_into` that isn't exposed to other processes yet.
+/// Mirrors `FixedDict::insert`'s swap-on-longer-distance rule so the probe-distance
+/// invariant `get`/`try_get` rely on for early-exit termination still holds afterward.
+fn robin_hood_insert(table: &mut [Slot], mask: usize, key: usize, index: IndexGenerator, counter: usize) {
+    let mut idx = key & mask;
+    let mut cur_key = key;
+    let mut cur_index = index;
+    let mut cur_counter = counter;
+
+    loop {
+        let slot = &mut table[idx];
+        if !slot.occupied {
+            slot.key = cur_key;
+            slot.index = cur_index;
+            slot.counter = cur_counter;
+            slot.occupied = true;
+            return;
+        }
+
+        if probe_distance(cur_key, idx, mask) > probe_distance(slot.key, idx, mask) {
+            let displaced_key = slot.key;
+            let displaced_index = slot.index;
+            let displaced_counter = slot.counter;
+            slot.key = cur_key;
+            slot.index = cur_index;
+            slot.counter = cur_counter;
+            cur_key = displaced_key;
+            cur_index = displaced_index;
+            cur_counter = displaced_counter;
+        }
+        idx = (idx + 1) & mask;
+    }
+}
+
+/// Backward-shift deletion: `i` is a just-vacated slot whose lock is already held.
+/// Walks forward from `i`, pulling back any occupied slot that can legally move
+/// closer to its home bucket, so no key is stranded past the hole left by `get`.
+/// Releases the lock of whichever slot ends up being the final hole.
+fn backward_shift_delete(table: &mut [Slot], mask: usize, start: usize, uid: u64) {
+    let mut i = start;
+    let mut j = (i + 1) & mask;
+
+    while j != start {
+        acquire_spin(&table[j].lock, uid);
+        if !table[j].occupied {
+            release(&table[j].lock);
+            break;
+        }
+
+        let home = table[j].key & mask;
+        if !in_cyclic_interval(home, i, j) {
+            table[i].key = table[j].key;
+            table[i].index = table[j].index;
+            table[i].counter = table[j].counter;
+            table[i].occupied = true;
+            table[j].occupied = false;
+            release(&table[i].lock);
+            i = j;
+        } else {
+            release(&table[j].lock);
+        }
+        j = (j + 1) & mask;
+    }
+    release(&table[i].lock);
+}
 
 #[repr(C)]
-#[derive(Copy, Clone)]
 struct Slot {
     pub key: usize,
     pub index: IndexGenerator,
     pub counter: usize,
     pub occupied: bool,
+    /// `0` when unlocked, otherwise the UID of the process/worker holding the slot.
+    pub lock: AtomicU64,
 }
 
 impl Default for Slot {
@@ -21,13 +107,48 @@ impl Default for Slot {
             index: IndexGenerator::new(1),
             counter: usize::MIN,
             occupied: false,
+            lock: AtomicU64::new(UNLOCKED),
         }
     }
 }
 
+/// Identifies a buffer as holding a `FixedDict` layout, distinct from `FixedList`'s.
+const DICT_MAGIC: [u8; 8] = *b"FXDDICT\0";
+/// Bumped whenever `DictHeader`/`Slot`'s on-wire layout changes incompatibly.
+const DICT_VERSION: u32 = 2;
+
+/// Load factor above which `set` rejects new keys with `PyOverflowError`, asking the
+/// caller to `rehash_into` a larger buffer first. Mirrors the rule of thumb for
+/// open-addressing tables: beyond this point probe chains get expensive.
+pub const HIGH_LOAD_FACTOR: f64 = 0.9;
+/// Load factor below which a table is considered sparse enough to shrink via
+/// `rehash_into` into a smaller buffer.
+pub const LOW_LOAD_FACTOR: f64 = 0.35;
+
+#[repr(C)]
 struct DictHeader {
+    magic: [u8; 8],
+    version: u32,
     capacity: usize,
     nb_of_queues: usize,
+    /// Live-key count. Atomic so concurrent `insert`/`try_set`/`get`/`try_get` calls
+    /// can update it without a lock; it is bumped or dropped after the slot lock is
+    /// released. The load-factor check reads it once before probing, outside any
+    /// slot lock, so concurrent inserts can each pass the check and together push
+    /// the load past `max_load_factor`.
+    len: AtomicUsize,
+}
+
+/// Checks a header read from an existing buffer against the capacity/nb_of_queues
+/// the caller expects, used by both `attach` and `open_mapped(create=false)`.
+fn validate_dict_header(stored: &DictHeader, capacity: usize, nb_of_queues: usize) -> PyResult<()> {
+    validate_header(
+        "FixedDict",
+        stored.magic, DICT_MAGIC,
+        stored.version, DICT_VERSION,
+        stored.capacity, capacity,
+        stored.nb_of_queues, nb_of_queues,
+    )
 }
 
 #[pyclass]
@@ -35,10 +156,77 @@ pub struct FixedDict {
     ptr: *mut u8,
     header: *mut DictHeader,
     slots: *mut Slot,
+    /// Owns the backing storage when constructed via `open_mapped`; `None` when the
+    /// caller supplied a raw pointer via `new`/`attach` and owns the memory itself.
+    mmap: Option<MmapMut>,
 }
 unsafe impl Send for FixedDict {}  // thread safety
 unsafe impl Sync for FixedDict {}  // thread safety
 
+impl FixedDict {
+    /// Shared Robin Hood insertion body for `set`/`set_weighted`/`set_stride`: `index`
+    /// carries whichever `IndexGenerator` policy the caller constructed.
+    ///
+    /// Hand-over-hand locking: when a slot gets displaced, the next candidate
+    /// slot's lock is acquired *before* the current slot is overwritten and its
+    /// lock released. This ensures the displaced key is never absent from the
+    /// table at any instant a concurrent `get`/`try_get` could observe — it either
+    /// still sits at the slot we're about to overwrite, or it's about to land in
+    /// the slot whose lock we're already holding.
+    fn insert(&self, key: usize, index: IndexGenerator, total_tasks: usize, uid: u64, max_load_factor: f64) -> PyResult<()> {
+        let header = unsafe { &*self.header };
+        if header.len.load(Ordering::Acquire) as f64 / header.capacity as f64 >= max_load_factor {
+            return Err(PyOverflowError::new_err(format!(
+                "Load factor is at or above {:.2}; call rehash_into with a larger capacity",
+                max_load_factor,
+            )));
+        }
+
+        let mask = header.capacity - 1;
+        let table = unsafe { slice::from_raw_parts_mut(self.slots, header.capacity) };
+        let mut idx = key & mask;
+        let mut cur_key = key;
+        let mut cur_index = index;
+        let mut cur_counter = total_tasks;
+
+        acquire_spin(&table[idx].lock, uid);
+        for _ in 0..header.capacity {
+            if !table[idx].occupied {
+                table[idx].key = cur_key;
+                table[idx].index = cur_index;
+                table[idx].counter = cur_counter;
+                table[idx].occupied = true;
+                release(&table[idx].lock);
+                header.len.fetch_add(1, Ordering::AcqRel);
+                return Ok(());
+            }
+
+            let next_idx = (idx + 1) & mask;
+            if probe_distance(cur_key, idx, mask) > probe_distance(table[idx].key, idx, mask) {
+                let displaced_key = table[idx].key;
+                let displaced_index = table[idx].index;
+                let displaced_counter = table[idx].counter;
+
+                acquire_spin(&table[next_idx].lock, uid);
+                table[idx].key = cur_key;
+                table[idx].index = cur_index;
+                table[idx].counter = cur_counter;
+                release(&table[idx].lock);
+
+                cur_key = displaced_key;
+                cur_index = displaced_index;
+                cur_counter = displaced_counter;
+            } else if next_idx != idx {
+                acquire_spin(&table[next_idx].lock, uid);
+                release(&table[idx].lock);
+            }
+            idx = next_idx;
+        }
+        release(&table[idx].lock);
+        Err(PyKeyError::new_err("No free slot"))
+    }
+}
+
 #[pymethods]
 impl FixedDict {
     /// ! `ptr` buffer >= capacity * size_of::<Slot>() bytes.
@@ -60,45 +248,271 @@ impl FixedDict {
 
         // Init in memory
         (*header) = DictHeader {
+            magic: DICT_MAGIC,
+            version: DICT_VERSION,
             capacity,
             nb_of_queues,
+            len: AtomicUsize::new(0),
         };
+        // Zero every slot, in particular `lock`: garbage there would make
+        // `acquire_spin` spin forever on a slot that was never actually locked.
+        for slot in slice::from_raw_parts_mut(slots, capacity) {
+            *slot = Slot::default();
+        }
 
-        Ok(FixedDict { ptr: raw_ptr, header, slots })
+        Ok(FixedDict { ptr: raw_ptr, header, slots, mmap: None })
     }
 
-    pub fn set(&self, key: usize, total_tasks: usize) -> PyResult<()> {
-        let header = unsafe { &mut *self.header };
-        let table = unsafe { slice::from_raw_parts_mut(self.slots, header.capacity) };
-        let mut idx = key & (header.capacity - 1);
+    /// Connects to a buffer already initialized by `new`, validating its header
+    /// instead of overwriting it. Use this from the second (and later) process that
+    /// maps the same shared region.
+    #[staticmethod]
+    pub unsafe fn attach(ptr: usize, capacity: usize, nb_of_queues: usize) -> PyResult<Self> {
+        if ptr == 0 {
+            return Err(PyValueError::new_err("Pointer cannot be null"));
+        }
+
+        let raw_ptr = ptr as *mut u8;
+        let header = raw_ptr as *mut DictHeader;
+        let slots = raw_ptr.add(std::mem::size_of::<DictHeader>()) as *mut Slot;
+
+        validate_dict_header(&*header, capacity, nb_of_queues)?;
+
+        Ok(FixedDict { ptr: raw_ptr, header, slots, mmap: None })
+    }
+
+    /// Memory-maps `path` and owns the mapping for the lifetime of the `FixedDict`,
+    /// so callers don't need a separate allocation or a raw pointer with its own
+    /// lifetime management. With `create = true` the file is extended to
+    /// `total_size(capacity)` and a fresh header is written; otherwise the existing
+    /// header is validated the same way `attach` validates a raw pointer.
+    #[staticmethod]
+    pub fn open_mapped(path: &str, capacity: usize, nb_of_queues: usize, create: bool) -> PyResult<Self> {
+        if capacity == 0 {
+            return Err(PyValueError::new_err("Capacity must be > 0"));
+        }
+        if capacity & (capacity - 1) != 0 {
+            return Err(PyValueError::new_err("Capacity must be a power of two"));
+        }
+
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(create)
+            .open(path)
+            .map_err(|e| PyValueError::new_err(format!("Failed to open {}: {}", path, e)))?;
+
+        if create {
+            file.set_len(Self::total_size(capacity) as u64)
+                .map_err(|e| PyValueError::new_err(format!("Failed to resize {}: {}", path, e)))?;
+        }
+
+        let mut mmap = unsafe {
+            MmapMut::map_mut(&file)
+                .map_err(|e| PyValueError::new_err(format!("Failed to map {}: {}", path, e)))?
+        };
+
+        let raw_ptr = mmap.as_mut_ptr();
+        let header = raw_ptr as *mut DictHeader;
+        let slots = unsafe { raw_ptr.add(std::mem::size_of::<DictHeader>()) as *mut Slot };
+
+        if create {
+            unsafe {
+                (*header) = DictHeader {
+                    magic: DICT_MAGIC,
+                    version: DICT_VERSION,
+                    capacity,
+                    nb_of_queues,
+                    len: AtomicUsize::new(0),
+                };
+                // Zero every slot: `set_len` to an unchanged file length is a no-op,
+                // so a reused file can still carry stale, non-zero lock words.
+                for slot in slice::from_raw_parts_mut(slots, capacity) {
+                    *slot = Slot::default();
+                }
+            }
+        } else {
+            validate_dict_header(unsafe { &*header }, capacity, nb_of_queues)?;
+        }
+
+        Ok(FixedDict { ptr: raw_ptr, header, slots, mmap: Some(mmap) })
+    }
+
+    /// Blocks (spins) on a contended slot. `uid` identifies the calling process/worker
+    /// and must be non-zero. Raises `PyOverflowError` instead of inserting when the
+    /// table's current load factor is already at or above `max_load_factor`; call
+    /// `rehash_into` first.
+    /// Uses Robin Hood probing: an element is swapped into a slot it passes if it has
+    /// travelled farther from its own home bucket, bounding the worst-case probe
+    /// length instead of letting one cluster grow unchecked.
+    #[pyo3(signature = (key, total_tasks, uid, max_load_factor = HIGH_LOAD_FACTOR))]
+    pub fn set(&self, key: usize, total_tasks: usize, uid: u64, max_load_factor: f64) -> PyResult<()> {
+        check_uid(uid)?;
+        let header = unsafe { &*self.header };
         let index = IndexGenerator::new(header.nb_of_queues);
-        let counter = total_tasks;
+        self.insert(key, index, total_tasks, uid, max_load_factor)
+    }
+
+    /// Same as `set`, but indices for this key are drawn via smooth weighted
+    /// round-robin instead of plain round-robin. `weights[i]` is queue `i`'s relative
+    /// share and `len(weights)` becomes the number of queues for this key.
+    #[pyo3(signature = (key, total_tasks, uid, weights, max_load_factor = HIGH_LOAD_FACTOR))]
+    pub fn set_weighted(&self, key: usize, total_tasks: usize, uid: u64, weights: Vec<usize>, max_load_factor: f64) -> PyResult<()> {
+        check_uid(uid)?;
+        let index = IndexGenerator::new_weighted(&weights)
+            .ok_or_else(|| PyValueError::new_err(format!(
+                "weights must be non-empty and at most {} entries",
+                crate::iterator::MAX_WEIGHTED_QUEUES,
+            )))?;
+        self.insert(key, index, total_tasks, uid, max_load_factor)
+    }
 
+    /// Same as `set`, but indices for this key are drawn via a deterministic
+    /// stride/hash walk seeded from `key`, so the same key always distributes the
+    /// same way across `nb_of_queues` queues.
+    #[pyo3(signature = (key, total_tasks, uid, nb_of_queues, max_load_factor = HIGH_LOAD_FACTOR))]
+    pub fn set_stride(&self, key: usize, total_tasks: usize, uid: u64, nb_of_queues: usize, max_load_factor: f64) -> PyResult<()> {
+        check_uid(uid)?;
+        let index = IndexGenerator::new_stride(nb_of_queues, key)
+            .ok_or_else(|| PyValueError::new_err("nb_of_queues must be > 0"))?;
+        self.insert(key, index, total_tasks, uid, max_load_factor)
+    }
+
+    /// Non-blocking `set`: raises `PyBlockingIOError` instead of spinning as soon as
+    /// a candidate slot is already locked by another uid. Uses the same Robin Hood
+    /// probing and hand-over-hand locking as `set`.
+    #[pyo3(signature = (key, total_tasks, uid, max_load_factor = HIGH_LOAD_FACTOR))]
+    pub fn try_set(&self, key: usize, total_tasks: usize, uid: u64, max_load_factor: f64) -> PyResult<bool> {
+        check_uid(uid)?;
+        let header = unsafe { &*self.header };
+        if header.len.load(Ordering::Acquire) as f64 / header.capacity as f64 >= max_load_factor {
+            return Err(PyOverflowError::new_err(format!(
+                "Load factor is at or above {:.2}; call rehash_into with a larger capacity",
+                max_load_factor,
+            )));
+        }
+
+        let mask = header.capacity - 1;
+        let table = unsafe { slice::from_raw_parts_mut(self.slots, header.capacity) };
+        let mut idx = key & mask;
+        let mut cur_key = key;
+        let mut cur_index = IndexGenerator::new(header.nb_of_queues);
+        let mut cur_counter = total_tasks;
+
+        if !try_acquire(&table[idx].lock, uid) {
+            return Err(PyBlockingIOError::new_err("Slot is locked by another process"));
+        }
         for _ in 0..header.capacity {
             if !table[idx].occupied {
-                table[idx] = Slot { key, index, counter, occupied: true };
-                return Ok(());
+                table[idx].key = cur_key;
+                table[idx].index = cur_index;
+                table[idx].counter = cur_counter;
+                table[idx].occupied = true;
+                release(&table[idx].lock);
+                header.len.fetch_add(1, Ordering::AcqRel);
+                return Ok(true);
             }
-            idx = (idx + 1) & (header.capacity - 1);
+
+            let next_idx = (idx + 1) & mask;
+            if probe_distance(cur_key, idx, mask) > probe_distance(table[idx].key, idx, mask) {
+                let displaced_key = table[idx].key;
+                let displaced_index = table[idx].index;
+                let displaced_counter = table[idx].counter;
+
+                if !try_acquire(&table[next_idx].lock, uid) {
+                    release(&table[idx].lock);
+                    return Err(PyBlockingIOError::new_err("Slot is locked by another process"));
+                }
+                table[idx].key = cur_key;
+                table[idx].index = cur_index;
+                table[idx].counter = cur_counter;
+                release(&table[idx].lock);
+
+                cur_key = displaced_key;
+                cur_index = displaced_index;
+                cur_counter = displaced_counter;
+            } else if next_idx != idx {
+                if !try_acquire(&table[next_idx].lock, uid) {
+                    release(&table[idx].lock);
+                    return Err(PyBlockingIOError::new_err("Slot is locked by another process"));
+                }
+                release(&table[idx].lock);
+            }
+            idx = next_idx;
         }
-        Err(PyKeyError::new_err("No free slot"))
+        release(&table[idx].lock);
+        Ok(false)
     }
 
-    pub fn get(&mut self, key: usize) -> PyResult<usize> {
-        let header = unsafe { &mut *self.header };
+    /// Blocks (spins) on a contended slot. `uid` identifies the calling process/worker
+    /// and must be non-zero. Stops as soon as the scanned probe distance exceeds the
+    /// occupant's own distance from home: under Robin Hood probing no entry can sit
+    /// farther from home than that, so the key cannot be present further on.
+    pub fn get(&mut self, key: usize, uid: u64) -> PyResult<usize> {
+        check_uid(uid)?;
+        let header = unsafe { &*self.header };
+        let mask = header.capacity - 1;
         let table = unsafe { slice::from_raw_parts_mut(self.slots, header.capacity) };
-        let mut idx = key & (header.capacity - 1);
+        let mut idx = key & mask;
 
         for _ in 0..header.capacity {
-            let slot = &mut table[idx];
-            if slot.occupied && slot.key == key {
-                let index = slot.index.next().unwrap();
-                slot.counter -= 1;
+            acquire_spin(&table[idx].lock, uid);
+            if table[idx].occupied && table[idx].key == key {
+                let index = table[idx].index.next().unwrap();
+                table[idx].counter -= 1;
 
-                if slot.counter == 0 { slot.occupied = false; } // pop key
+                if table[idx].counter == 0 {
+                    table[idx].occupied = false; // pop key
+                    backward_shift_delete(table, mask, idx, uid);
+                    header.len.fetch_sub(1, Ordering::AcqRel);
+                } else {
+                    release(&table[idx].lock);
+                }
                 return Ok(index);
             }
-            idx = (idx + 1) & (header.capacity - 1);
+            if !table[idx].occupied || probe_distance(key, idx, mask) > probe_distance(table[idx].key, idx, mask) {
+                release(&table[idx].lock);
+                return Err(PyKeyError::new_err("Key not found"));
+            }
+            release(&table[idx].lock);
+            idx = (idx + 1) & mask;
+        }
+        Err(PyKeyError::new_err("Key not found"))
+    }
+
+    /// Non-blocking `get`: raises `PyBlockingIOError` instead of spinning as soon as
+    /// a candidate slot is already locked by another uid. Uses the same Robin Hood
+    /// early-exit as `get`.
+    pub fn try_get(&mut self, key: usize, uid: u64) -> PyResult<usize> {
+        check_uid(uid)?;
+        let header = unsafe { &*self.header };
+        let mask = header.capacity - 1;
+        let table = unsafe { slice::from_raw_parts_mut(self.slots, header.capacity) };
+        let mut idx = key & mask;
+
+        for _ in 0..header.capacity {
+            if !try_acquire(&table[idx].lock, uid) {
+                return Err(PyBlockingIOError::new_err("Slot is locked by another process"));
+            }
+            if table[idx].occupied && table[idx].key == key {
+                let index = table[idx].index.next().unwrap();
+                table[idx].counter -= 1;
+
+                if table[idx].counter == 0 {
+                    table[idx].occupied = false; // pop key
+                    backward_shift_delete(table, mask, idx, uid);
+                    header.len.fetch_sub(1, Ordering::AcqRel);
+                } else {
+                    release(&table[idx].lock);
+                }
+                return Ok(index);
+            }
+            if !table[idx].occupied || probe_distance(key, idx, mask) > probe_distance(table[idx].key, idx, mask) {
+                release(&table[idx].lock);
+                return Err(PyKeyError::new_err("Key not found"));
+            }
+            release(&table[idx].lock);
+            idx = (idx + 1) & mask;
         }
         Err(PyKeyError::new_err("Key not found"))
     }
@@ -107,8 +521,158 @@ impl FixedDict {
         self.ptr as usize
     }
 
+    /// Number of occupied slots.
+    pub fn len(&self) -> usize {
+        unsafe { (*self.header).len.load(Ordering::Acquire) }
+    }
+
+    pub fn capacity(&self) -> usize {
+        unsafe { (*self.header).capacity }
+    }
+
+    pub fn load_factor(&self) -> f64 {
+        let header = unsafe { &*self.header };
+        header.len.load(Ordering::Acquire) as f64 / header.capacity as f64
+    }
+
     #[staticmethod]
     pub fn total_size(capacity: usize) -> usize {
         std::mem::size_of::<DictHeader>() + capacity * std::mem::size_of::<Slot>()
     }
+
+    /// Reinserts every live key into a fresh buffer of `new_capacity` slots at
+    /// `new_ptr`, preserving each key's remaining `counter` and `IndexGenerator`
+    /// state, then repoints this `FixedDict` at the new buffer. `new_capacity` must
+    /// be a power of two. Use this proactively once `load_factor` crosses
+    /// `HIGH_LOAD_FACTOR` (grow) or drops below `LOW_LOAD_FACTOR` (shrink).
+    pub unsafe fn rehash_into(&mut self, new_ptr: usize, new_capacity: usize) -> PyResult<()> {
+        if new_capacity == 0 {
+            return Err(PyValueError::new_err("Capacity must be > 0"));
+        }
+        if new_capacity & (new_capacity - 1) != 0 {
+            return Err(PyValueError::new_err("Capacity must be a power of two"));
+        }
+        if new_ptr == 0 {
+            return Err(PyValueError::new_err("Pointer cannot be null"));
+        }
+
+        let old_header = &*self.header;
+        let old_capacity = old_header.capacity;
+        let nb_of_queues = old_header.nb_of_queues;
+        let old_table = slice::from_raw_parts(self.slots, old_capacity);
+
+        let new_raw_ptr = new_ptr as *mut u8;
+        let new_header_ptr = new_raw_ptr as *mut DictHeader;
+        let new_slots_ptr = new_raw_ptr.add(std::mem::size_of::<DictHeader>()) as *mut Slot;
+        let new_table = slice::from_raw_parts_mut(new_slots_ptr, new_capacity);
+        for slot in new_table.iter_mut() {
+            *slot = Slot::default();
+        }
+
+        let mask = new_capacity - 1;
+        let mut len = 0usize;
+        for old_slot in old_table {
+            if !old_slot.occupied {
+                continue;
+            }
+            robin_hood_insert(new_table, mask, old_slot.key, old_slot.index, old_slot.counter);
+            len += 1;
+        }
+
+        (*new_header_ptr) = DictHeader {
+            magic: DICT_MAGIC,
+            version: DICT_VERSION,
+            capacity: new_capacity,
+            nb_of_queues,
+            len: AtomicUsize::new(len),
+        };
+
+        self.ptr = new_raw_ptr;
+        self.header = new_header_ptr;
+        self.slots = new_slots_ptr;
+        self.mmap = None;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Backing buffer plus a `FixedDict` pointed at it. The buffer must outlive the
+    /// dict, so both are returned together; dropping the tuple frees both at once.
+    fn make_dict(capacity: usize, nb_of_queues: usize) -> (Vec<u8>, FixedDict) {
+        let mut buf = vec![0u8; FixedDict::total_size(capacity)];
+        let dict = unsafe { FixedDict::new(buf.as_mut_ptr() as usize, capacity, nb_of_queues).unwrap() };
+        (buf, dict)
+    }
+
+    #[test]
+    fn robin_hood_insert_keeps_earlier_key_reachable_after_displacement() {
+        let (_buf, mut dict) = make_dict(4, 1);
+        // 1 sits at its home slot 1 and 0 at its home slot 0. Key 4 (home 0) then
+        // reaches slot 1 one step from home, farther than 1 (0 steps), so it takes
+        // slot 1 and displaces 1 into slot 2, exercising the swap-on-longer-distance
+        // rule.
+        dict.set(1, 1, 1, HIGH_LOAD_FACTOR).unwrap();
+        dict.set(0, 1, 1, HIGH_LOAD_FACTOR).unwrap();
+        dict.set(4, 1, 1, HIGH_LOAD_FACTOR).unwrap();
+        assert_eq!(dict.get(1, 1).unwrap(), 0);
+        assert_eq!(dict.get(4, 1).unwrap(), 0);
+        assert_eq!(dict.get(0, 1).unwrap(), 0);
+    }
+
+    #[test]
+    fn backward_shift_delete_keeps_later_colliding_keys_reachable() {
+        let (_buf, mut dict) = make_dict(4, 1);
+        // 0, 4 and 8 all collide on home bucket 0 and land in slots 0, 1, 2 in
+        // insertion order.
+        dict.set(0, 1, 1, HIGH_LOAD_FACTOR).unwrap();
+        dict.set(4, 1, 1, HIGH_LOAD_FACTOR).unwrap();
+        dict.set(8, 1, 1, HIGH_LOAD_FACTOR).unwrap();
+
+        // Removing 0 vacates slot 0, the home bucket of 4 and 8. `get` stops at the
+        // first empty slot, so without backward-shift both would be reported as
+        // missing; the shift moves them back into slots 0 and 1.
+        assert_eq!(dict.get(0, 1).unwrap(), 0);
+        assert_eq!(dict.get(4, 1).unwrap(), 0);
+        assert_eq!(dict.get(8, 1).unwrap(), 0);
+        assert!(dict.get(0, 1).is_err());
+    }
+
+    #[test]
+    fn rehash_into_preserves_every_key_and_counter() {
+        let (_buf, mut dict) = make_dict(4, 1);
+        dict.set(0, 3, 1, HIGH_LOAD_FACTOR).unwrap();
+        dict.set(1, 2, 1, HIGH_LOAD_FACTOR).unwrap();
+        dict.set(2, 1, 1, HIGH_LOAD_FACTOR).unwrap();
+
+        let mut new_buf = vec![0u8; FixedDict::total_size(8)];
+        unsafe {
+            dict.rehash_into(new_buf.as_mut_ptr() as usize, 8).unwrap();
+        }
+
+        assert_eq!(dict.capacity(), 8);
+        assert_eq!(dict.len(), 3);
+        // Each key's counter survived the rehash: 3 pops for key 0, then it's gone.
+        assert_eq!(dict.get(0, 1).unwrap(), 0);
+        assert_eq!(dict.get(0, 1).unwrap(), 0);
+        assert_eq!(dict.get(0, 1).unwrap(), 0);
+        assert!(dict.get(0, 1).is_err());
+        assert_eq!(dict.get(1, 1).unwrap(), 0);
+        assert_eq!(dict.get(2, 1).unwrap(), 0);
+    }
+
+    #[test]
+    fn set_rejects_zero_uid() {
+        let (_buf, dict) = make_dict(4, 1);
+        assert!(dict.set(0, 1, 0, HIGH_LOAD_FACTOR).is_err());
+    }
+
+    #[test]
+    fn get_rejects_zero_uid() {
+        let (_buf, mut dict) = make_dict(4, 1);
+        dict.set(0, 1, 1, HIGH_LOAD_FACTOR).unwrap();
+        assert!(dict.get(0, 0).is_err());
+    }
 }
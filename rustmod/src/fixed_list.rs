@@ -1,30 +1,75 @@
 use std::{usize, slice};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use pyo3::prelude::*;
-use pyo3::exceptions::{PyValueError};
+use pyo3::exceptions::{PyValueError, PyBlockingIOError};
+use memmap2::MmapMut;
 
 use crate::iterator::IndexGenerator;
-
+use crate::locking::{acquire_spin, try_acquire, release, check_uid, validate_header, UNLOCKED};
 
 #[repr(C)]
-#[derive(Copy, Clone)]
 struct Slot {
     pub index: IndexGenerator,
     pub counter: usize,
+    /// `0` when unlocked, otherwise the UID of the process/worker holding the slot.
+    pub lock: AtomicU64,
 }
 impl Default for Slot {
     fn default() -> Self {
         Slot {
             index: IndexGenerator::new(1),
             counter: usize::MIN,
+            lock: AtomicU64::new(UNLOCKED),
         }
     }
 }
 
+/// Identifies a buffer as holding a `FixedList` layout, distinct from `FixedDict`'s.
+const LIST_MAGIC: [u8; 8] = *b"FXDLIST\0";
+/// Bumped whenever `ListHeader`/`Slot`'s on-wire layout changes incompatibly.
+const LIST_VERSION: u32 = 2;
+
 #[repr(C)]
 struct ListHeader {
+    magic: [u8; 8],
+    version: u32,
     capacity: usize,
     nb_of_queues: usize,
-    end_index: usize,
+    /// Atomic so concurrent `push`/`next` calls can reserve/retire a slot index as
+    /// part of the same critical section that checks it, instead of mutating a bare
+    /// field after the per-slot lock has already been released (which let two
+    /// processes both observe a stale `end_index` and clobber the same slot).
+    end_index: AtomicUsize,
+}
+
+/// Atomically claims the next free slot index for `push`, bumping `end_index` only
+/// if doing so stays within `capacity`. This runs before the slot itself is ever
+/// touched, so two concurrent pushers can never be handed the same index.
+fn reserve_slot(end_index: &AtomicUsize, capacity: usize) -> PyResult<usize> {
+    loop {
+        let current = end_index.load(Ordering::Acquire);
+        if current >= capacity {
+            return Err(PyValueError::new_err("FixedList is full"));
+        }
+        if end_index
+            .compare_exchange(current, current + 1, Ordering::AcqRel, Ordering::Acquire)
+            .is_ok()
+        {
+            return Ok(current);
+        }
+    }
+}
+
+/// Checks a header read from an existing buffer against the capacity/nb_of_queues
+/// the caller expects, used by both `attach` and `open_mapped(create=false)`.
+fn validate_list_header(stored: &ListHeader, capacity: usize, nb_of_queues: usize) -> PyResult<()> {
+    validate_header(
+        "FixedList",
+        stored.magic, LIST_MAGIC,
+        stored.version, LIST_VERSION,
+        stored.capacity, capacity,
+        stored.nb_of_queues, nb_of_queues,
+    )
 }
 
 #[pyclass]
@@ -32,10 +77,31 @@ pub struct FixedList {
     ptr: *mut u8,
     header: *mut ListHeader,
     slots: *mut Slot,
+    /// Owns the backing storage when constructed via `open_mapped`; `None` when the
+    /// caller supplied a raw pointer via `new`/`attach` and owns the memory itself.
+    mmap: Option<MmapMut>,
 }
 unsafe impl Send for FixedList {}  // thread safety
 unsafe impl Sync for FixedList {}  // thread safety
 
+impl FixedList {
+    /// Shared body for `add`/`add_weighted`/`add_stride`: `index` carries whichever
+    /// `IndexGenerator` policy the caller constructed.
+    fn push(&mut self, index: IndexGenerator, total_tasks: usize, uid: u64) -> PyResult<()> {
+        check_uid(uid)?;
+        let header = unsafe { &*self.header };
+        let table = unsafe { slice::from_raw_parts_mut(self.slots, header.capacity) };
+
+        let reserved = reserve_slot(&header.end_index, header.capacity)?;
+        let slot = &mut table[reserved];
+        acquire_spin(&slot.lock, uid);
+        slot.index = index;
+        slot.counter = total_tasks;
+        release(&slot.lock);
+        Ok(())
+    }
+}
+
 #[pymethods]
 impl FixedList {
     #[new]
@@ -56,43 +122,210 @@ impl FixedList {
 
         // Init in memory
         (*header) = ListHeader {
+            magic: LIST_MAGIC,
+            version: LIST_VERSION,
             capacity,
             nb_of_queues,
-            end_index: 0,
+            end_index: AtomicUsize::new(0),
         };
-        
-        Ok(FixedList { ptr: raw_ptr, header, slots })
+        // Zero every slot, in particular `lock`: garbage there would make
+        // `acquire_spin` spin forever on a slot that was never actually locked.
+        for slot in slice::from_raw_parts_mut(slots, capacity) {
+            *slot = Slot::default();
+        }
+
+        Ok(FixedList { ptr: raw_ptr, header, slots, mmap: None })
     }
 
-    pub fn add(&mut self, total_tasks: usize) -> PyResult<()> {
-        let header = unsafe { &mut *self.header };
-        let table = unsafe { slice::from_raw_parts_mut(self.slots, header.capacity) };
+    /// Connects to a buffer already initialized by `new`, validating its header
+    /// instead of overwriting it. Use this from the second (and later) process that
+    /// maps the same shared region.
+    #[staticmethod]
+    pub unsafe fn attach(ptr: usize, capacity: usize, nb_of_queues: usize) -> PyResult<Self> {
+        if ptr == 0 {
+            return Err(PyValueError::new_err("Pointer cannot be null"));
+        }
 
-        if header.end_index >= header.capacity {
-            return Err(PyValueError::new_err("FixedList is full"));
+        let raw_ptr = ptr as *mut u8;
+        let header = raw_ptr as *mut ListHeader;
+        let slots = raw_ptr.add(std::mem::size_of::<ListHeader>()) as *mut Slot;
+
+        validate_list_header(&*header, capacity, nb_of_queues)?;
+
+        Ok(FixedList { ptr: raw_ptr, header, slots, mmap: None })
+    }
+
+    /// Memory-maps `path` and owns the mapping for the lifetime of the `FixedList`,
+    /// so callers don't need a separate allocation or a raw pointer with its own
+    /// lifetime management. With `create = true` the file is extended to
+    /// `total_size(capacity)` and a fresh header is written; otherwise the existing
+    /// header is validated the same way `attach` validates a raw pointer.
+    #[staticmethod]
+    pub fn open_mapped(path: &str, capacity: usize, nb_of_queues: usize, create: bool) -> PyResult<Self> {
+        if capacity == 0 {
+            return Err(PyValueError::new_err("Capacity must be > 0"));
+        }
+        if capacity & (capacity - 1) != 0 {
+            return Err(PyValueError::new_err("Capacity must be a power of two"));
         }
-        
-        let index = IndexGenerator::new(header.nb_of_queues);
 
-        table[header.end_index] = Slot { index: index, counter: total_tasks };
-        header.end_index += 1;
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(create)
+            .open(path)
+            .map_err(|e| PyValueError::new_err(format!("Failed to open {}: {}", path, e)))?;
+
+        if create {
+            file.set_len(Self::total_size(capacity) as u64)
+                .map_err(|e| PyValueError::new_err(format!("Failed to resize {}: {}", path, e)))?;
+        }
+
+        let mut mmap = unsafe {
+            MmapMut::map_mut(&file)
+                .map_err(|e| PyValueError::new_err(format!("Failed to map {}: {}", path, e)))?
+        };
+
+        let raw_ptr = mmap.as_mut_ptr();
+        let header = raw_ptr as *mut ListHeader;
+        let slots = unsafe { raw_ptr.add(std::mem::size_of::<ListHeader>()) as *mut Slot };
+
+        if create {
+            unsafe {
+                (*header) = ListHeader {
+                    magic: LIST_MAGIC,
+                    version: LIST_VERSION,
+                    capacity,
+                    nb_of_queues,
+                    end_index: AtomicUsize::new(0),
+                };
+                // Zero every slot: `set_len` to an unchanged file length is a no-op,
+                // so a reused file can still carry stale, non-zero lock words.
+                for slot in slice::from_raw_parts_mut(slots, capacity) {
+                    *slot = Slot::default();
+                }
+            }
+        } else {
+            validate_list_header(unsafe { &*header }, capacity, nb_of_queues)?;
+        }
+
+        Ok(FixedList { ptr: raw_ptr, header, slots, mmap: Some(mmap) })
+    }
+
+    /// Blocks (spins) on a contended slot. `uid` identifies the calling process/worker
+    /// and must be non-zero.
+    pub fn add(&mut self, total_tasks: usize, uid: u64) -> PyResult<()> {
+        let nb_of_queues = unsafe { (*self.header).nb_of_queues };
+        let index = IndexGenerator::new(nb_of_queues);
+        self.push(index, total_tasks, uid)
+    }
+
+    /// Same as `add`, but indices popped for this entry are drawn via smooth
+    /// weighted round-robin instead of plain round-robin. `weights[i]` is queue
+    /// `i`'s relative share and `len(weights)` becomes the number of queues for
+    /// this entry.
+    pub fn add_weighted(&mut self, total_tasks: usize, uid: u64, weights: Vec<usize>) -> PyResult<()> {
+        let index = IndexGenerator::new_weighted(&weights)
+            .ok_or_else(|| PyValueError::new_err(format!(
+                "weights must be non-empty and at most {} entries",
+                crate::iterator::MAX_WEIGHTED_QUEUES,
+            )))?;
+        self.push(index, total_tasks, uid)
+    }
+
+    /// Same as `add`, but indices popped for this entry are drawn via a
+    /// deterministic stride/hash walk seeded from `seed`.
+    pub fn add_stride(&mut self, total_tasks: usize, uid: u64, nb_of_queues: usize, seed: usize) -> PyResult<()> {
+        let index = IndexGenerator::new_stride(nb_of_queues, seed)
+            .ok_or_else(|| PyValueError::new_err("nb_of_queues must be > 0"))?;
+        self.push(index, total_tasks, uid)
+    }
+
+    /// Non-blocking `add`: raises `PyBlockingIOError` instead of spinning when the
+    /// target slot is already locked by another uid.
+    pub fn try_add(&mut self, total_tasks: usize, uid: u64) -> PyResult<()> {
+        check_uid(uid)?;
+        let header = unsafe { &*self.header };
+        let table = unsafe { slice::from_raw_parts_mut(self.slots, header.capacity) };
+
+        let reserved = reserve_slot(&header.end_index, header.capacity)?;
+        let index = IndexGenerator::new(header.nb_of_queues);
+        let slot = &mut table[reserved];
+        if !try_acquire(&slot.lock, uid) {
+            return Err(PyBlockingIOError::new_err("Slot is locked by another process"));
+        }
+        slot.index = index;
+        slot.counter = total_tasks;
+        release(&slot.lock);
         Ok(())
     }
 
-    pub fn next(&mut self) -> PyResult<usize> {
-        let header = unsafe { &mut *self.header };
+    /// Blocks (spins) on a contended slot. `uid` identifies the calling process/worker
+    /// and must be non-zero.
+    pub fn next(&mut self, uid: u64) -> PyResult<usize> {
+        check_uid(uid)?;
+        let header = unsafe { &*self.header };
+        let table = unsafe { slice::from_raw_parts_mut(self.slots, header.capacity) };
+
+        loop {
+            let current = header.end_index.load(Ordering::Acquire);
+            if current == 0 {
+                return Err(PyValueError::new_err("List is empty"));
+            }
+
+            let idx = current - 1;
+            let slot = &mut table[idx];
+            acquire_spin(&slot.lock, uid);
+            if header.end_index.load(Ordering::Acquire) != current {
+                // Another `next`/`try_next` retired this slot while we were waiting
+                // for its lock; the top has moved, so recompute it from scratch.
+                release(&slot.lock);
+                continue;
+            }
 
-        if header.end_index == 0 {
-            return Err(PyValueError::new_err("List is empty"));
+            let index = slot.index.next().unwrap();
+            slot.counter -= 1;
+            if slot.counter == 0 {
+                header.end_index.store(idx, Ordering::Release);
+            }
+            release(&slot.lock);
+            return Ok(index);
         }
+    }
 
+    /// Non-blocking `next`: raises `PyBlockingIOError` instead of spinning when the
+    /// target slot is already locked by another uid.
+    pub fn try_next(&mut self, uid: u64) -> PyResult<usize> {
+        check_uid(uid)?;
+        let header = unsafe { &*self.header };
         let table = unsafe { slice::from_raw_parts_mut(self.slots, header.capacity) };
-        let slot = &mut table[header.end_index - 1];
-        let index = slot.index.next().unwrap();
 
-        slot.counter -= 1;
-        if slot.counter == 0 { header.end_index -= 1; }
-        Ok(index)
+        loop {
+            let current = header.end_index.load(Ordering::Acquire);
+            if current == 0 {
+                return Err(PyValueError::new_err("List is empty"));
+            }
+
+            let idx = current - 1;
+            let slot = &mut table[idx];
+            if !try_acquire(&slot.lock, uid) {
+                return Err(PyBlockingIOError::new_err("Slot is locked by another process"));
+            }
+            if header.end_index.load(Ordering::Acquire) != current {
+                // Another `next`/`try_next` retired this slot first; the top has
+                // moved, so recompute it from scratch.
+                release(&slot.lock);
+                continue;
+            }
+
+            let index = slot.index.next().unwrap();
+            slot.counter -= 1;
+            if slot.counter == 0 {
+                header.end_index.store(idx, Ordering::Release);
+            }
+            release(&slot.lock);
+            return Ok(index);
+        }
     }
 
     pub fn base_address(&self) -> usize {
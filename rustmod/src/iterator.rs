@@ -1,15 +1,113 @@
+/// Upper bound on the number of queues a weighted policy can track in-line.
+/// Kept fixed-size (rather than a `Vec`) so `IndexGenerator` stays `Copy`, holds no
+/// heap pointer, and can be stored directly inside a shared-memory `Slot`.
+pub const MAX_WEIGHTED_QUEUES: usize = 16;
+
+#[repr(u8)]
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum DistributionPolicy {
+    /// `(current_index + 1) % nb_of_queues`. The default, for backward compatibility.
+    RoundRobin,
+    /// Smooth weighted round-robin: a queue with weight `w` receives a share of the
+    /// indices proportional to `w` instead of an equal share.
+    Weighted,
+    /// Deterministic stride/hash seeded from a key, so the same key always walks the
+    /// same sequence of queues.
+    Stride,
+}
+
 #[derive(Copy, Clone)]
 pub struct IndexGenerator {
     pub nb_of_queues: usize,
     pub current_index: usize,
+    pub policy: DistributionPolicy,
+    /// Weighted-policy only: per-queue weight and running "current weight" state
+    /// for the smooth weighted round-robin algorithm. Unused by other policies.
+    pub weights: [i64; MAX_WEIGHTED_QUEUES],
+    pub current_weights: [i64; MAX_WEIGHTED_QUEUES],
+    /// Stride-policy only: the key this generator was seeded from. Unused by other
+    /// policies.
+    pub seed: usize,
 }
 
 impl IndexGenerator {
+    /// Plain round-robin, identical to the original behavior.
     pub fn new(nb_of_queues: usize) -> Self {
         Self {
             nb_of_queues,
             current_index: 0,
+            policy: DistributionPolicy::RoundRobin,
+            weights: [0; MAX_WEIGHTED_QUEUES],
+            current_weights: [0; MAX_WEIGHTED_QUEUES],
+            seed: 0,
+        }
+    }
+
+    /// Smooth weighted round-robin: `weights[i]` is the relative share queue `i`
+    /// should receive. `weights.len()` becomes `nb_of_queues`.
+    pub fn new_weighted(weights: &[usize]) -> Option<Self> {
+        if weights.is_empty() || weights.len() > MAX_WEIGHTED_QUEUES {
+            return None;
+        }
+        let mut w = [0i64; MAX_WEIGHTED_QUEUES];
+        for (i, &weight) in weights.iter().enumerate() {
+            w[i] = weight as i64;
+        }
+        Some(Self {
+            nb_of_queues: weights.len(),
+            current_index: 0,
+            policy: DistributionPolicy::Weighted,
+            weights: w,
+            current_weights: [0; MAX_WEIGHTED_QUEUES],
+            seed: 0,
+        })
+    }
+
+    /// Deterministic stride/hash mode: always walks the same sequence of queues for
+    /// a given `seed` (typically the key being distributed). Returns `None` if
+    /// `nb_of_queues` is `0`, since `next_stride` would otherwise divide by it.
+    pub fn new_stride(nb_of_queues: usize, seed: usize) -> Option<Self> {
+        if nb_of_queues == 0 {
+            return None;
+        }
+        Some(Self {
+            nb_of_queues,
+            current_index: 0,
+            policy: DistributionPolicy::Stride,
+            weights: [0; MAX_WEIGHTED_QUEUES],
+            current_weights: [0; MAX_WEIGHTED_QUEUES],
+            seed,
+        })
+    }
+
+    fn next_round_robin(&mut self) -> usize {
+        let result = self.current_index;
+        self.current_index = (self.current_index + 1) % self.nb_of_queues;
+        result
+    }
+
+    fn next_weighted(&mut self) -> usize {
+        let n = self.nb_of_queues;
+        let mut total: i64 = 0;
+        for i in 0..n {
+            self.current_weights[i] += self.weights[i];
+            total += self.weights[i];
+        }
+        let mut best = 0;
+        for i in 1..n {
+            if self.current_weights[i] > self.current_weights[best] {
+                best = i;
+            }
         }
+        self.current_weights[best] -= total;
+        best
+    }
+
+    fn next_stride(&mut self) -> usize {
+        let step = self.current_index;
+        self.current_index += 1;
+        let h = self.seed ^ step.wrapping_mul(0x9E3779B97F4A7C15);
+        h % self.nb_of_queues
     }
 }
 
@@ -17,8 +115,56 @@ impl Iterator for IndexGenerator {
     type Item = usize;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let result = self.current_index;
-        self.current_index = (self.current_index + 1) % self.nb_of_queues;
-        Some(result)
+        Some(match self.policy {
+            DistributionPolicy::RoundRobin => self.next_round_robin(),
+            DistributionPolicy::Weighted => self.next_weighted(),
+            DistributionPolicy::Stride => self.next_stride(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_robin_cycles_through_every_queue() {
+        let mut gen = IndexGenerator::new(3);
+        let drawn: Vec<usize> = (0..6).map(|_| gen.next().unwrap()).collect();
+        assert_eq!(drawn, vec![0, 1, 2, 0, 1, 2]);
+    }
+
+    #[test]
+    fn weighted_shares_indices_in_proportion_to_weight() {
+        // Queue 0 has twice the weight of queue 1, so it should receive twice as
+        // many of the drawn indices over a long enough run.
+        let mut gen = IndexGenerator::new_weighted(&[2, 1]).unwrap();
+        let mut counts = [0usize; 2];
+        for _ in 0..300 {
+            counts[gen.next().unwrap()] += 1;
+        }
+        let ratio = counts[0] as f64 / counts[1] as f64;
+        assert!((ratio - 2.0).abs() < 0.1, "expected ~2:1 split, got {:?}", counts);
+    }
+
+    #[test]
+    fn stride_is_deterministic_per_seed() {
+        let mut a = IndexGenerator::new_stride(4, 42).unwrap();
+        let mut b = IndexGenerator::new_stride(4, 42).unwrap();
+        let drawn_a: Vec<usize> = (0..8).map(|_| a.next().unwrap()).collect();
+        let drawn_b: Vec<usize> = (0..8).map(|_| b.next().unwrap()).collect();
+        assert_eq!(drawn_a, drawn_b);
+        assert!(drawn_a.iter().all(|&i| i < 4));
+    }
+
+    #[test]
+    fn stride_rejects_zero_queues() {
+        assert!(IndexGenerator::new_stride(0, 42).is_none());
+    }
+
+    #[test]
+    fn weighted_rejects_empty_and_oversized_weights() {
+        assert!(IndexGenerator::new_weighted(&[]).is_none());
+        assert!(IndexGenerator::new_weighted(&vec![1; MAX_WEIGHTED_QUEUES + 1]).is_none());
     }
 }
@@ -1,6 +1,7 @@
 use pyo3::prelude::*;
 
 mod iterator;
+mod locking;
 mod fixed_dict;
 mod fixed_list;
 pub use fixed_dict::FixedDict;
@@ -0,0 +1,75 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+
+/// Sentinel stored in a slot's lock word when no process/worker currently owns it.
+/// Shared by `FixedDict` and `FixedList`, whose slot layouts both end in an
+/// `AtomicU64` lock.
+pub const UNLOCKED: u64 = 0;
+
+/// Spin until the slot's lock is acquired for `uid`.
+pub fn acquire_spin(lock: &AtomicU64, uid: u64) {
+    while lock.compare_exchange(UNLOCKED, uid, Ordering::Acquire, Ordering::Relaxed).is_err() {
+        std::hint::spin_loop();
+    }
+}
+
+/// Try once to acquire the slot's lock for `uid`, without spinning.
+pub fn try_acquire(lock: &AtomicU64, uid: u64) -> bool {
+    lock.compare_exchange(UNLOCKED, uid, Ordering::Acquire, Ordering::Relaxed).is_ok()
+}
+
+pub fn release(lock: &AtomicU64) {
+    lock.store(UNLOCKED, Ordering::Release);
+}
+
+/// Every public method that takes a `uid` requires it non-zero: `UNLOCKED` doubles
+/// as the sentinel meaning "no owner", so a `uid` of `0` would let
+/// `compare_exchange(0, 0, ..)` trivially "succeed" without ever actually marking
+/// the slot locked, silently defeating the whole locking scheme.
+pub fn check_uid(uid: u64) -> PyResult<()> {
+    if uid == 0 {
+        return Err(PyValueError::new_err("uid must be non-zero"));
+    }
+    Ok(())
+}
+
+/// Checks a header read from an existing buffer against the magic/version this
+/// build expects and the capacity/nb_of_queues the caller expects. Shared by
+/// `FixedDict`'s and `FixedList`'s `attach`/`open_mapped(create=false)`, which
+/// each pass their own `DictHeader`/`ListHeader` fields through.
+pub fn validate_header(
+    type_name: &str,
+    magic: [u8; 8],
+    expected_magic: [u8; 8],
+    version: u32,
+    expected_version: u32,
+    capacity: usize,
+    expected_capacity: usize,
+    nb_of_queues: usize,
+    expected_nb_of_queues: usize,
+) -> PyResult<()> {
+    if magic != expected_magic {
+        return Err(PyValueError::new_err(format!("Buffer does not hold a {} (bad magic)", type_name)));
+    }
+    if version != expected_version {
+        return Err(PyValueError::new_err(format!(
+            "{} version mismatch: buffer has {}, expected {}",
+            type_name, version, expected_version,
+        )));
+    }
+    if capacity != expected_capacity {
+        return Err(PyValueError::new_err(format!(
+            "{} capacity mismatch: buffer has {}, expected {}",
+            type_name, capacity, expected_capacity,
+        )));
+    }
+    if nb_of_queues != expected_nb_of_queues {
+        return Err(PyValueError::new_err(format!(
+            "{} nb_of_queues mismatch: buffer has {}, expected {}",
+            type_name, nb_of_queues, expected_nb_of_queues,
+        )));
+    }
+    Ok(())
+}